@@ -0,0 +1,261 @@
+use crate::profile::fetch_profiles;
+use crate::provider::SymbolProvider;
+use chrono::Utc;
+use sqlx::{PgPool, Row};
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use tracing::info;
+
+/// A single symbol's refreshed profile fields, staged for the batch update.
+struct RefreshedProfile {
+    symbol: String,
+    name: Option<String>,
+    sector: Option<String>,
+    industry: Option<String>,
+    country: Option<String>,
+    ipo_date: Option<chrono::NaiveDate>,
+    market_cap: Option<i64>,
+    data_source: String,
+}
+
+/// Hashes `symbol` into one of `num_partitions` shards, so backfill work can
+/// be split into independent, resumable batches.
+fn partition_of(symbol: &str, num_partitions: u32) -> u32 {
+    let mut hasher = DefaultHasher::new();
+    symbol.hash(&mut hasher);
+    (hasher.finish() % num_partitions as u64) as u32
+}
+
+fn stale_after_days() -> i64 {
+    env::var("BACKFILL_STALE_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7)
+}
+
+fn num_symbol_partitions() -> u32 {
+    env::var("NUM_SYMBOL_PARTITIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(4)
+        .max(1)
+}
+
+/// A partition whose `job_status` row reports success more recently than
+/// this many seconds ago is skipped on the next backfill run, so a retried
+/// or cron-triggered backfill doesn't redo partitions that already
+/// completed.
+fn resume_window_secs() -> i64 {
+    env::var("BACKFILL_RESUME_WINDOW_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3600)
+}
+
+/// Returns true if `partition_id` already completed successfully within the
+/// resume window, and can be skipped.
+async fn partition_recently_succeeded(
+    pool: &PgPool,
+    partition_id: u32,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let row = sqlx::query(
+        "SELECT status, last_run FROM job_status WHERE job_name = $1 ORDER BY last_run DESC LIMIT 1",
+    )
+    .bind(format!("symbol_backfill_partition_{}", partition_id))
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(match row {
+        Some(row) => {
+            let status: String = row.get("status");
+            let last_run: chrono::DateTime<Utc> = row.get("last_run");
+            let age_secs = (Utc::now() - last_run).num_seconds();
+            status == "success" && age_secs <= resume_window_secs()
+        }
+        None => false,
+    })
+}
+
+/// Returns true when the binary should run in backfill mode instead of the
+/// default incremental delta sync: either `--backfill` was passed, or
+/// `RUN_MODE=backfill` is set in the environment.
+pub fn is_backfill_requested() -> bool {
+    env::args().any(|arg| arg == "--backfill")
+        || env::var("RUN_MODE")
+            .map(|v| v.eq_ignore_ascii_case("backfill"))
+            .unwrap_or(false)
+}
+
+/// Re-fetches and reconciles profiles for every active symbol whose
+/// `last_updated` is older than `BACKFILL_STALE_DAYS` days, so stale market
+/// caps, IPO dates, and industries get refreshed. Work is sharded into
+/// `NUM_SYMBOL_PARTITIONS` independent batches by hash of symbol, run
+/// concurrently, each recorded with its own `job_status` row. A partition
+/// that succeeded within `BACKFILL_RESUME_WINDOW_SECS` is skipped, so a
+/// retried run resumes instead of redoing already-completed partitions.
+pub async fn run_backfill(
+    pool: &PgPool,
+    providers: &Arc<Vec<Arc<dyn SymbolProvider>>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let stale_days = stale_after_days();
+    let num_partitions = num_symbol_partitions();
+    info!(
+        "Starting backfill: {} partitions, symbols stale after {} days",
+        num_partitions, stale_days
+    );
+
+    let cutoff = Utc::now() - chrono::Duration::days(stale_days);
+    let stale_symbols: Vec<String> = sqlx::query(
+        "SELECT symbol FROM symbol_master WHERE is_active = TRUE AND last_updated < $1",
+    )
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| row.get("symbol"))
+    .collect();
+
+    let partitions = futures::future::join_all((0..num_partitions).map(|partition_id| {
+        let pool = pool.clone();
+        let providers = Arc::clone(providers);
+        let partition_symbols: Vec<String> = stale_symbols
+            .iter()
+            .filter(|s| partition_of(s, num_partitions) == partition_id)
+            .cloned()
+            .collect();
+        async move { run_partition(&pool, &providers, partition_id, num_partitions, partition_symbols).await }
+    }))
+    .await;
+
+    for result in &partitions {
+        if let Err(e) = result {
+            tracing::error!("Backfill partition failed: {}", e);
+        }
+    }
+    partitions.into_iter().collect::<Result<Vec<()>, _>>()?;
+
+    info!("Backfill complete: {} symbols refreshed across {} partitions", stale_symbols.len(), num_partitions);
+    Ok(())
+}
+
+/// Refreshes one partition's stale symbols, skipping it entirely if it
+/// already succeeded recently (see [`partition_recently_succeeded`]).
+async fn run_partition(
+    pool: &PgPool,
+    providers: &Arc<Vec<Arc<dyn SymbolProvider>>>,
+    partition_id: u32,
+    num_partitions: u32,
+    partition_symbols: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if partition_recently_succeeded(pool, partition_id).await? {
+        info!(
+            "Backfill partition {}/{}: skipped, succeeded within resume window",
+            partition_id, num_partitions
+        );
+        return Ok(());
+    }
+
+    info!(
+        "Backfill partition {}/{}: {} stale symbols",
+        partition_id,
+        num_partitions,
+        partition_symbols.len()
+    );
+
+    let fetched = fetch_profiles(providers, partition_symbols).await;
+
+    let mut refreshed = Vec::new();
+    for (symbol, data_source, profile) in fetched {
+        refreshed.push(RefreshedProfile {
+            symbol,
+            name: profile.name,
+            sector: profile.industry.clone(),
+            industry: profile.industry,
+            country: profile.country,
+            ipo_date: profile.ipo_date,
+            market_cap: profile.market_cap,
+            data_source,
+        });
+    }
+
+    let mut tx = pool.begin().await?;
+    for record in &refreshed {
+        sqlx::query(
+            r#"
+            UPDATE symbol_master
+            SET name = $1, sector = $2, industry = $3, country = $4,
+                ipo_date = $5, market_cap = $6, data_source = $7, last_updated = $8
+            WHERE symbol = $9
+        "#,
+        )
+        .bind(&record.name)
+        .bind(&record.sector)
+        .bind(&record.industry)
+        .bind(&record.country)
+        .bind(record.ipo_date)
+        .bind(record.market_cap)
+        .bind(&record.data_source)
+        .bind(Utc::now())
+        .bind(&record.symbol)
+        .execute(tx.as_mut())
+        .await?;
+    }
+    tx.commit().await?;
+
+    sqlx::query("INSERT INTO job_status (job_name, last_run, status, details) VALUES ($1, $2, $3, $4)")
+        .bind(format!("symbol_backfill_partition_{}", partition_id))
+        .bind(Utc::now())
+        .bind("success")
+        .bind(format!("Refreshed {} symbols", refreshed.len()))
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// `num_symbol_partitions` reads from the process environment, so tests
+    /// that set `NUM_SYMBOL_PARTITIONS` must not run concurrently with each
+    /// other.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn partition_of_is_deterministic_and_in_range() {
+        let num_partitions = 4;
+        let first = partition_of("AAPL", num_partitions);
+        let second = partition_of("AAPL", num_partitions);
+        assert_eq!(first, second);
+
+        for symbol in ["AAPL", "MSFT", "GOOG", "TSLA"] {
+            assert!(partition_of(symbol, num_partitions) < num_partitions);
+        }
+    }
+
+    #[test]
+    fn partition_of_with_one_partition_always_returns_zero() {
+        for symbol in ["AAPL", "MSFT", "GOOG"] {
+            assert_eq!(partition_of(symbol, 1), 0);
+        }
+    }
+
+    #[test]
+    fn num_symbol_partitions_clamps_zero_to_one() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("NUM_SYMBOL_PARTITIONS", "0");
+        assert_eq!(num_symbol_partitions(), 1);
+        env::remove_var("NUM_SYMBOL_PARTITIONS");
+    }
+
+    #[test]
+    fn num_symbol_partitions_defaults_to_four_when_unset() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::remove_var("NUM_SYMBOL_PARTITIONS");
+        assert_eq!(num_symbol_partitions(), 4);
+    }
+}
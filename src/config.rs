@@ -0,0 +1,50 @@
+use serde::Deserialize;
+use std::env;
+use std::fs;
+
+/// One exchange to sync symbols for, as listed in `markets.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExchangeConfig {
+    /// Finnhub exchange code, e.g. `"US"`, `"L"`, `"T"`.
+    pub code: String,
+    /// Overrides the currency reported by the exchange's symbol listing,
+    /// for venues where Finnhub omits or misreports it.
+    pub currency_override: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Top-level `markets.json` document: the set of exchanges this service syncs.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    pub exchanges: Vec<ExchangeConfig>,
+}
+
+impl Config {
+    /// Loads `markets.json` from the path in `MARKETS_CONFIG_PATH`, falling
+    /// back to `markets.json` in the working directory.
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = env::var("MARKETS_CONFIG_PATH").unwrap_or_else(|_| "markets.json".to_string());
+        let raw = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read markets config at {}: {}", path, e))?;
+        let config: Config = serde_json::from_str(&raw)?;
+        Ok(config)
+    }
+
+    /// Exchanges with `enabled` set (or unset, since it defaults to true).
+    pub fn enabled_exchanges(&self) -> impl Iterator<Item = &ExchangeConfig> {
+        self.exchanges.iter().filter(|e| e.enabled)
+    }
+}
+
+/// Reads `MAX_PG_POOL_CONNS` from the environment, defaulting to 5.
+pub fn max_pg_pool_conns() -> u32 {
+    env::var("MAX_PG_POOL_CONNS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(5)
+}
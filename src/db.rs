@@ -0,0 +1,44 @@
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
+use sqlx::{Pool, Postgres};
+use std::env;
+use std::str::FromStr;
+
+/// Builds the Postgres pool, enabling mutual TLS when `USE_SSL=true`.
+///
+/// Plaintext connections (the default) keep using the bare `DATABASE_URL`.
+/// When SSL is requested, the CA root certificate and client certificate/key
+/// pair are loaded from `CA_CERT_PATH` / `CLIENT_CERT_PATH` /
+/// `CLIENT_KEY_PATH` and verified in full (`PgSslMode::VerifyFull`), for
+/// deployments against managed Postgres instances that require it.
+pub async fn connect_pool(
+    database_url: &str,
+    max_connections: u32,
+) -> Result<Pool<Postgres>, Box<dyn std::error::Error>> {
+    let use_ssl = env::var("USE_SSL")
+        .map(|v| v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+
+    if !use_ssl {
+        return Ok(PgPoolOptions::new()
+            .max_connections(max_connections)
+            .connect(database_url)
+            .await?);
+    }
+
+    let ca_cert_path = env::var("CA_CERT_PATH").expect("CA_CERT_PATH must be set when USE_SSL=true");
+    let client_cert_path =
+        env::var("CLIENT_CERT_PATH").expect("CLIENT_CERT_PATH must be set when USE_SSL=true");
+    let client_key_path =
+        env::var("CLIENT_KEY_PATH").expect("CLIENT_KEY_PATH must be set when USE_SSL=true");
+
+    let connect_options = PgConnectOptions::from_str(database_url)?
+        .ssl_mode(PgSslMode::VerifyFull)
+        .ssl_root_cert(&ca_cert_path)
+        .ssl_client_cert(&client_cert_path)
+        .ssl_client_key(&client_key_path);
+
+    Ok(PgPoolOptions::new()
+        .max_connections(max_connections)
+        .connect_with(connect_options)
+        .await?)
+}
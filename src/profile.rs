@@ -0,0 +1,28 @@
+use crate::provider::{self, NormalizedProfile, SymbolProvider};
+use futures::stream::{self, StreamExt};
+use std::sync::Arc;
+
+/// How many symbols may be in flight at once. Each provider enforces its
+/// own request-rate quota internally, so this just bounds overall
+/// concurrency rather than the request rate itself.
+const FETCH_CONCURRENCY: usize = 10;
+
+/// Fetches and reconciles profiles for many symbols concurrently, querying
+/// every enabled provider for each symbol. Returns each symbol alongside
+/// the winning provider's name (for `data_source`) and the merged profile.
+pub async fn fetch_profiles(
+    providers: &Arc<Vec<Arc<dyn SymbolProvider>>>,
+    symbols: Vec<String>,
+) -> Vec<(String, String, NormalizedProfile)> {
+    stream::iter(symbols)
+        .map(|symbol| {
+            let providers = Arc::clone(providers);
+            async move {
+                let (source, profile) = provider::fetch_and_reconcile(&providers, &symbol).await;
+                (symbol, source, profile)
+            }
+        })
+        .buffer_unordered(FETCH_CONCURRENCY)
+        .collect()
+        .await
+}
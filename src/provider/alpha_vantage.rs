@@ -0,0 +1,99 @@
+use super::{NormalizedProfile, NormalizedSymbol, SymbolProvider};
+use async_trait::async_trait;
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use metrics::counter;
+use reqwest::Client;
+use serde::Deserialize;
+use std::error::Error;
+use std::num::NonZeroU32;
+use tracing::error;
+
+#[derive(Deserialize, Default)]
+struct RawOverview {
+    #[serde(rename = "Name")]
+    name: Option<String>,
+    #[serde(rename = "Country")]
+    country: Option<String>,
+    #[serde(rename = "Industry")]
+    industry: Option<String>,
+    #[serde(rename = "MarketCapitalization")]
+    market_cap: Option<String>,
+}
+
+/// A secondary profile source, enabled when `ALPHA_VANTAGE_API_KEY` is set.
+/// Alpha Vantage's free tier allows 5 calls/min, enforced with its own
+/// limiter independent of Finnhub's quota.
+///
+/// Alpha Vantage has no bulk "list every symbol on this exchange" endpoint
+/// (only keyword search), so `list_symbols` always returns empty and this
+/// provider is never used as the primary listing source — only to
+/// cross-check profile fields for symbols Finnhub already listed.
+pub struct AlphaVantageProvider {
+    client: Client,
+    api_key: String,
+    limiter: DefaultDirectRateLimiter,
+}
+
+impl AlphaVantageProvider {
+    pub fn new(client: Client, api_key: String) -> Self {
+        Self {
+            client,
+            api_key,
+            limiter: RateLimiter::direct(Quota::per_minute(NonZeroU32::new(5).unwrap())),
+        }
+    }
+}
+
+#[async_trait]
+impl SymbolProvider for AlphaVantageProvider {
+    fn name(&self) -> &'static str {
+        "AlphaVantage"
+    }
+
+    async fn list_symbols(
+        &self,
+        _exchange: &str,
+    ) -> Result<Vec<NormalizedSymbol>, Box<dyn Error + Send + Sync>> {
+        Ok(Vec::new())
+    }
+
+    async fn fetch_profile(&self, symbol: &str) -> NormalizedProfile {
+        self.limiter.until_ready().await;
+        let raw = match self
+            .client
+            .get("https://www.alphavantage.co/query")
+            .query(&[
+                ("function", "OVERVIEW"),
+                ("symbol", symbol),
+                ("apikey", self.api_key.as_str()),
+            ])
+            .send()
+            .await
+        {
+            Ok(response) => {
+                counter!("symbol_sync_api_calls", 1, "endpoint" => "overview");
+                match response.json::<RawOverview>().await {
+                    Ok(overview) => overview,
+                    Err(e) => {
+                        error!("Failed to parse AlphaVantage overview for {}: {}", symbol, e);
+                        counter!("symbol_sync_errors", 1, "type" => "api_parse");
+                        RawOverview::default()
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to fetch AlphaVantage overview for {}: {}", symbol, e);
+                counter!("symbol_sync_errors", 1, "type" => "api_fetch");
+                RawOverview::default()
+            }
+        };
+
+        NormalizedProfile {
+            name: raw.name,
+            country: raw.country,
+            ipo_date: None,
+            market_cap: raw.market_cap.and_then(|cap| cap.parse::<i64>().ok()),
+            industry: raw.industry,
+        }
+    }
+}
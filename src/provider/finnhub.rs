@@ -0,0 +1,139 @@
+use super::{NormalizedProfile, NormalizedSymbol, SymbolProvider};
+use async_trait::async_trait;
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use metrics::counter;
+use reqwest::Client;
+use serde::Deserialize;
+use std::error::Error;
+use std::num::NonZeroU32;
+use std::time::Duration;
+use tracing::{error, warn};
+
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_SECS: u64 = 2;
+
+#[derive(Deserialize)]
+struct RawSymbol {
+    symbol: String,
+    mic: Option<String>,
+    currency: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct RawProfile {
+    name: Option<String>,
+    country: Option<String>,
+    ipo: Option<String>,
+    #[serde(rename = "marketCapitalization")]
+    market_cap: Option<f64>,
+    #[serde(rename = "finnhubIndustry")]
+    industry: Option<String>,
+}
+
+/// The original, and still primary, symbol listing and profile source.
+/// Finnhub's free tier allows 60 calls/min, enforced here with its own
+/// limiter so other providers' quotas don't interfere.
+pub struct FinnhubProvider {
+    client: Client,
+    api_key: String,
+    limiter: DefaultDirectRateLimiter,
+}
+
+impl FinnhubProvider {
+    pub fn new(client: Client, api_key: String) -> Self {
+        Self {
+            client,
+            api_key,
+            limiter: RateLimiter::direct(Quota::per_minute(NonZeroU32::new(60).unwrap())),
+        }
+    }
+}
+
+#[async_trait]
+impl SymbolProvider for FinnhubProvider {
+    fn name(&self) -> &'static str {
+        "Finnhub"
+    }
+
+    async fn list_symbols(
+        &self,
+        exchange: &str,
+    ) -> Result<Vec<NormalizedSymbol>, Box<dyn Error + Send + Sync>> {
+        self.limiter.until_ready().await;
+        let response = self
+            .client
+            .get("https://finnhub.io/api/v1/stock/symbol")
+            .query(&[("exchange", exchange), ("token", &self.api_key)])
+            .send()
+            .await?;
+        let raw: Vec<RawSymbol> = response.json().await?;
+        Ok(raw
+            .into_iter()
+            .map(|s| NormalizedSymbol {
+                symbol: s.symbol,
+                exchange: s.mic,
+                currency: s.currency,
+            })
+            .collect())
+    }
+
+    async fn fetch_profile(&self, symbol: &str) -> NormalizedProfile {
+        let mut attempt = 0;
+        let raw = loop {
+            self.limiter.until_ready().await;
+            match self
+                .client
+                .get("https://finnhub.io/api/v1/stock/profile2")
+                .query(&[("symbol", symbol), ("token", &self.api_key)])
+                .send()
+                .await
+            {
+                Ok(response) => {
+                    if response.status() == 429 {
+                        counter!("symbol_sync_errors", 1, "type" => "rate_limit");
+                        if attempt >= MAX_RETRIES {
+                            error!("Max retries reached for {}", symbol);
+                            break RawProfile::default();
+                        }
+                        let backoff = BASE_BACKOFF_SECS * 2u64.pow(attempt);
+                        warn!(
+                            "Rate limit hit for {}. Retrying after {}s (attempt {}/{})",
+                            symbol,
+                            backoff,
+                            attempt + 1,
+                            MAX_RETRIES
+                        );
+                        tokio::time::sleep(Duration::from_secs(backoff)).await;
+                        attempt += 1;
+                        continue;
+                    }
+                    counter!("symbol_sync_api_calls", 1, "endpoint" => "profile2");
+                    match response.json().await {
+                        Ok(profile) => break profile,
+                        Err(e) => {
+                            error!("Failed to parse profile for {}: {}", symbol, e);
+                            counter!("symbol_sync_errors", 1, "type" => "api_parse");
+                            break RawProfile::default();
+                        }
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to fetch profile for {}: {}", symbol, e);
+                    counter!("symbol_sync_errors", 1, "type" => "api_fetch");
+                    break RawProfile::default();
+                }
+            }
+        };
+
+        NormalizedProfile {
+            name: raw.name,
+            country: raw.country,
+            ipo_date: raw
+                .ipo
+                .as_deref()
+                .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()),
+            market_cap: raw.market_cap.map(|cap| (cap * 1_000_000.0) as i64),
+            industry: raw.industry,
+        }
+    }
+}
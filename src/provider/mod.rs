@@ -0,0 +1,177 @@
+pub mod alpha_vantage;
+pub mod finnhub;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::Client;
+use std::env;
+use std::error::Error;
+use std::sync::Arc;
+
+/// A symbol as reported by a provider's exchange listing, before it's
+/// tagged with the exchange code and currency configured in `markets.json`.
+#[derive(Debug, Clone)]
+pub struct NormalizedSymbol {
+    pub symbol: String,
+    pub exchange: Option<String>,
+    pub currency: Option<String>,
+}
+
+/// A symbol's profile fields as reported by one provider, normalized to a
+/// common shape so results from different providers can be reconciled.
+#[derive(Debug, Clone, Default)]
+pub struct NormalizedProfile {
+    pub name: Option<String>,
+    pub country: Option<String>,
+    pub ipo_date: Option<NaiveDate>,
+    pub market_cap: Option<i64>,
+    pub industry: Option<String>,
+}
+
+/// A source of symbol listings and profile data. Implementations own their
+/// own HTTP client and rate limiting, since different providers have
+/// different quotas.
+#[async_trait]
+pub trait SymbolProvider: Send + Sync {
+    /// Short, stable identifier recorded in `symbol_master.data_source`.
+    fn name(&self) -> &'static str;
+
+    async fn list_symbols(
+        &self,
+        exchange: &str,
+    ) -> Result<Vec<NormalizedSymbol>, Box<dyn Error + Send + Sync>>;
+
+    async fn fetch_profile(&self, symbol: &str) -> NormalizedProfile;
+}
+
+/// Builds the set of enabled providers: Finnhub is always the primary
+/// (listing) provider, and a second provider is added when its API key is
+/// configured in the environment.
+pub fn build_providers(client: Client, finnhub_api_key: String) -> Vec<Arc<dyn SymbolProvider>> {
+    let mut providers: Vec<Arc<dyn SymbolProvider>> =
+        vec![Arc::new(finnhub::FinnhubProvider::new(client.clone(), finnhub_api_key))];
+
+    if let Ok(alpha_vantage_api_key) = env::var("ALPHA_VANTAGE_API_KEY") {
+        providers.push(Arc::new(alpha_vantage::AlphaVantageProvider::new(
+            client,
+            alpha_vantage_api_key,
+        )));
+    }
+
+    providers
+}
+
+/// Counts the non-null fields in `profile`, as a tie-break for candidates
+/// that agree on market cap presence and IPO date: an empty profile (e.g. a
+/// provider that errored or got rate-limited and fell back to its default)
+/// should never outrank one with actual data.
+fn completeness_score(profile: &NormalizedProfile) -> u8 {
+    [
+        profile.name.is_some(),
+        profile.country.is_some(),
+        profile.ipo_date.is_some(),
+        profile.market_cap.is_some(),
+        profile.industry.is_some(),
+    ]
+    .iter()
+    .filter(|present| **present)
+    .count() as u8
+}
+
+/// Ranks a profile for reconciliation: a non-null market cap wins first,
+/// then the most recent IPO date, then overall field completeness.
+fn profile_rank(profile: &NormalizedProfile) -> (bool, Option<NaiveDate>, u8) {
+    (profile.market_cap.is_some(), profile.ipo_date, completeness_score(profile))
+}
+
+/// Fetches `symbol`'s profile from every provider and reconciles the
+/// results into one profile using [`profile_rank`]. Ties are broken in
+/// favor of the earlier provider (i.e. the primary listing provider), so an
+/// empty fallback profile from a later, failed provider never displaces
+/// real data. Returns the winning provider's name alongside the merged
+/// profile, for the `data_source` column.
+pub async fn fetch_and_reconcile(
+    providers: &[Arc<dyn SymbolProvider>],
+    symbol: &str,
+) -> (String, NormalizedProfile) {
+    let mut candidates = Vec::with_capacity(providers.len());
+    for provider in providers {
+        let profile = provider.fetch_profile(symbol).await;
+        candidates.push((provider.name().to_string(), profile));
+    }
+
+    reconcile(candidates)
+}
+
+/// Picks the best-ranked candidate, keeping the earliest on an exact tie.
+fn reconcile(candidates: Vec<(String, NormalizedProfile)>) -> (String, NormalizedProfile) {
+    let mut winner: Option<(String, NormalizedProfile)> = None;
+    for candidate in candidates {
+        winner = Some(match winner {
+            None => candidate,
+            Some(current) => {
+                if profile_rank(&candidate.1) > profile_rank(&current.1) {
+                    candidate
+                } else {
+                    current
+                }
+            }
+        });
+    }
+    winner.unwrap_or_else(|| ("unknown".to_string(), NormalizedProfile::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile_with_name(name: &str) -> NormalizedProfile {
+        NormalizedProfile {
+            name: Some(name.to_string()),
+            country: Some("US".to_string()),
+            industry: Some("Tech".to_string()),
+            ipo_date: None,
+            market_cap: None,
+        }
+    }
+
+    #[test]
+    fn tie_on_market_cap_and_ipo_date_prefers_non_empty_profile() {
+        let candidates = vec![
+            ("Finnhub".to_string(), profile_with_name("Acme Corp")),
+            ("AlphaVantage".to_string(), NormalizedProfile::default()),
+        ];
+
+        let (source, profile) = reconcile(candidates);
+
+        assert_eq!(source, "Finnhub");
+        assert_eq!(profile.name.as_deref(), Some("Acme Corp"));
+    }
+
+    #[test]
+    fn tie_on_market_cap_and_ipo_date_prefers_first_when_both_empty() {
+        let candidates = vec![
+            ("Finnhub".to_string(), NormalizedProfile::default()),
+            ("AlphaVantage".to_string(), NormalizedProfile::default()),
+        ];
+
+        let (source, _) = reconcile(candidates);
+
+        assert_eq!(source, "Finnhub");
+    }
+
+    #[test]
+    fn non_null_market_cap_wins_even_if_listed_second() {
+        let mut with_cap = profile_with_name("Acme Corp");
+        with_cap.market_cap = Some(1_000_000);
+        let candidates = vec![
+            ("Finnhub".to_string(), NormalizedProfile::default()),
+            ("AlphaVantage".to_string(), with_cap),
+        ];
+
+        let (source, profile) = reconcile(candidates);
+
+        assert_eq!(source, "AlphaVantage");
+        assert_eq!(profile.market_cap, Some(1_000_000));
+    }
+}
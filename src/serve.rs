@@ -0,0 +1,117 @@
+use crate::config::Config;
+use crate::provider::SymbolProvider;
+use crate::sync::run_sync;
+use actix_web::{get, web, App, HttpResponse, HttpServer};
+use chrono::{DateTime, Utc};
+use metrics_exporter_prometheus::PrometheusHandle;
+use sqlx::{PgPool, Row};
+use std::env;
+use std::sync::Arc;
+use tokio::time::{interval, Duration};
+use tracing::{error, info};
+
+/// Returns true when the binary should run as a long-lived service instead
+/// of a one-shot sync: either `--serve` was passed, or `RUN_MODE=serve` is
+/// set in the environment.
+pub fn is_serve_requested() -> bool {
+    env::args().any(|arg| arg == "--serve")
+        || env::var("RUN_MODE")
+            .map(|v| v.eq_ignore_ascii_case("serve"))
+            .unwrap_or(false)
+}
+
+fn sync_interval_secs() -> u64 {
+    env::var("SYNC_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3600)
+}
+
+/// `/health` reports unhealthy once the last successful `symbol_sync` row is
+/// older than this many seconds, defaulting to twice the sync interval.
+fn health_freshness_secs() -> i64 {
+    env::var("HEALTH_FRESHNESS_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| sync_interval_secs() as i64 * 2)
+}
+
+struct AppState {
+    pool: PgPool,
+    metrics_handle: PrometheusHandle,
+}
+
+#[get("/metrics")]
+async fn metrics(state: web::Data<AppState>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(state.metrics_handle.render())
+}
+
+#[get("/health")]
+async fn health(state: web::Data<AppState>) -> HttpResponse {
+    let row = sqlx::query(
+        "SELECT status, last_run FROM job_status WHERE job_name = 'symbol_sync' ORDER BY last_run DESC LIMIT 1",
+    )
+    .fetch_optional(&state.pool)
+    .await;
+
+    match row {
+        Ok(Some(row)) => {
+            let status: String = row.get("status");
+            let last_run: DateTime<Utc> = row.get("last_run");
+            let age_secs = (Utc::now() - last_run).num_seconds();
+            if status == "success" && age_secs <= health_freshness_secs() {
+                HttpResponse::Ok().body("ok")
+            } else {
+                HttpResponse::ServiceUnavailable()
+                    .body(format!("last symbol_sync run status={} age={}s", status, age_secs))
+            }
+        }
+        Ok(None) => HttpResponse::ServiceUnavailable().body("no symbol_sync job_status recorded yet"),
+        Err(e) => {
+            error!("Health check query failed: {}", e);
+            HttpResponse::ServiceUnavailable().body("health check query failed")
+        }
+    }
+}
+
+/// Runs the incremental sync on a `SYNC_INTERVAL_SECS` loop while serving
+/// `/metrics` and `/health`, turning the otherwise one-shot binary into a
+/// scrapeable long-lived service.
+pub async fn run_server(
+    pool: PgPool,
+    providers: Arc<Vec<Arc<dyn SymbolProvider>>>,
+    markets: Config,
+    metrics_handle: PrometheusHandle,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let state = web::Data::new(AppState {
+        pool: pool.clone(),
+        metrics_handle,
+    });
+
+    let http_server = HttpServer::new(move || {
+        App::new()
+            .app_data(state.clone())
+            .service(metrics)
+            .service(health)
+    })
+    .bind(("0.0.0.0", 8080))?
+    .run();
+
+    let sync_loop = async move {
+        let mut ticker = interval(Duration::from_secs(sync_interval_secs()));
+        loop {
+            ticker.tick().await;
+            info!("Starting scheduled sync");
+            if let Err(e) = run_sync(&pool, &providers, &markets).await {
+                error!("Scheduled sync failed: {}", e);
+            }
+        }
+    };
+
+    tokio::select! {
+        result = http_server => result.map_err(Into::into),
+        _ = sync_loop => Ok(()),
+    }
+}
@@ -0,0 +1,148 @@
+use crate::config::Config;
+use crate::provider::SymbolProvider;
+use crate::SymbolMaster;
+use chrono::Utc;
+use metrics::{counter, gauge};
+use sqlx::{PgPool, Row};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// Outcome of one incremental delta sync: how many symbols were added,
+/// delisted, and listed in total across the configured exchanges.
+pub struct SyncSummary {
+    pub new: usize,
+    pub delisted: usize,
+    pub total: usize,
+}
+
+/// Diffs the live symbol list for every configured exchange against active
+/// rows, fetches and reconciles profiles for the new symbols across every
+/// enabled provider, upserts the new symbols atomically in one transaction,
+/// and then marks delisted symbols inactive in a separate statement. This is
+/// the incremental delta sync; see [`crate::backfill::run_backfill`] for the
+/// separate stale-profile refresh.
+///
+/// The first provider is treated as the primary listing source — it decides
+/// which symbols exist per exchange — while every enabled provider is
+/// queried for profile data, reconciled per symbol.
+pub async fn run_sync(
+    pool: &PgPool,
+    providers: &Arc<Vec<Arc<dyn SymbolProvider>>>,
+    markets: &Config,
+) -> Result<SyncSummary, Box<dyn std::error::Error>> {
+    info!("Starting symbol_master sync at {}", Utc::now());
+    let listing_provider = providers.first().expect("at least one provider must be configured");
+
+    // Fetch the live symbol list for every configured exchange, tagging each
+    // symbol with the exchange code it came from.
+    let mut symbols: Vec<(String, Option<String>, String)> = Vec::new();
+    for exchange in markets.enabled_exchanges() {
+        info!("Fetching symbols for exchange {} from {}", exchange.code, listing_provider.name());
+        let exchange_symbols = listing_provider.list_symbols(&exchange.code).await?;
+        symbols.extend(exchange_symbols.into_iter().map(|s| {
+            let currency = exchange.currency_override.clone().or(s.currency);
+            (exchange.code.clone(), currency, s.symbol)
+        }));
+    }
+    gauge!("symbol_sync_total_symbols", symbols.len() as f64);
+
+    // Get existing active symbols
+    let existing_symbols: Vec<String> = sqlx::query("SELECT symbol FROM symbol_master WHERE is_active = TRUE")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| row.get("symbol"))
+        .collect();
+
+    // Identify new and delisted symbols
+    let symbol_set: HashSet<String> = symbols.iter().map(|(_, _, symbol)| symbol.clone()).collect();
+    let existing_set: HashSet<String> = existing_symbols.into_iter().collect();
+    let new_symbols: Vec<&(String, Option<String>, String)> = symbols
+        .iter()
+        .filter(|(_, _, symbol)| !existing_set.contains(symbol))
+        .collect();
+    let delisted_symbols: Vec<String> = existing_set.difference(&symbol_set).cloned().collect();
+    gauge!("symbol_sync_new_symbols", new_symbols.len() as f64);
+    gauge!("symbol_sync_delisted_symbols", delisted_symbols.len() as f64);
+
+    // Fetch and reconcile profiles for all new symbols concurrently.
+    info!("Fetching profiles for {} new symbols across {} provider(s)", new_symbols.len(), providers.len());
+    let symbol_names: Vec<String> = new_symbols.iter().map(|(_, _, symbol)| symbol.clone()).collect();
+    let profiles: HashMap<String, (String, crate::provider::NormalizedProfile)> =
+        crate::profile::fetch_profiles(providers, symbol_names)
+            .await
+            .into_iter()
+            .map(|(symbol, source, profile)| (symbol, (source, profile)))
+            .collect();
+
+    let mut records = Vec::new();
+    for (exchange_code, currency, symbol) in new_symbols {
+        let (data_source, profile) = profiles
+            .get(symbol)
+            .cloned()
+            .unwrap_or_else(|| ("unknown".to_string(), Default::default()));
+        let record = SymbolMaster {
+            symbol: symbol.clone(),
+            exchange: Some(exchange_code.clone()),
+            name: profile.name,
+            sector: profile.industry.clone(),
+            industry: profile.industry,
+            currency: currency.clone(),
+            country: profile.country,
+            ipo_date: profile.ipo_date,
+            market_cap: profile.market_cap,
+            is_active: true,
+            data_source,
+            last_updated: Utc::now(),
+        };
+        records.push(record);
+    }
+
+    // Upsert new symbols in a single batch of multi-row statements, atomically.
+    let mut tx = pool.begin().await?;
+    crate::upsert::upsert_symbols(&mut tx, &records).await?;
+    tx.commit().await?;
+
+    // Handle delistings
+    if !delisted_symbols.is_empty() {
+        sqlx::query(r#"
+            UPDATE symbol_master
+            SET is_active = FALSE, last_updated = $1
+            WHERE symbol = ANY($2)
+        "#)
+        .bind(Utc::now())
+        .bind(&delisted_symbols)
+        .execute(pool)
+        .await?;
+    }
+
+    // Validate data
+    let row = sqlx::query("SELECT COUNT(*) AS total FROM symbol_master WHERE is_active = TRUE")
+        .fetch_one(pool)
+        .await?;
+    let active_count: i64 = row.get("total");
+    gauge!("symbol_sync_active_symbols", active_count as f64);
+    if active_count < (symbols.len() as f64 * 0.9) as i64 {
+        error!("Active symbols ({}) much lower than expected ({})", active_count, symbols.len());
+        counter!("symbol_sync_errors", 1, "type" => "data_validation");
+    }
+
+    // Record job completion
+    sqlx::query("INSERT INTO job_status (job_name, last_run, status, details) VALUES ($1, $2, $3, $4)")
+        .bind("symbol_sync")
+        .bind(Utc::now())
+        .bind("success")
+        .bind(format!("Added {} new, delisted {}", records.len(), delisted_symbols.len()))
+        .execute(pool)
+        .await?;
+
+    info!("Completed sync: {} new, {} delisted, total {}", records.len(), delisted_symbols.len(), symbols.len());
+    counter!("symbol_sync_completed", 1);
+
+    Ok(SyncSummary {
+        new: records.len(),
+        delisted: delisted_symbols.len(),
+        total: symbols.len(),
+    })
+}
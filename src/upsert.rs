@@ -0,0 +1,133 @@
+use crate::SymbolMaster;
+use sqlx::{Postgres, QueryBuilder, Transaction};
+
+/// Postgres allows at most 65535 bind parameters per statement; with 12
+/// columns per row that's ~5461 rows, but we stay well under that to leave
+/// headroom and keep individual statements fast.
+const MAX_ROWS_PER_STATEMENT: usize = 1000;
+
+/// Builds one parameterized `INSERT ... VALUES (...), (...) ON CONFLICT`
+/// statement that upserts every row in `records`.
+///
+/// Callers are expected to pass a chunk of at most [`MAX_ROWS_PER_STATEMENT`]
+/// records; see [`upsert_symbols`] for the chunked, transactional entry point.
+pub fn build_symbol_upsert_statement(records: &[SymbolMaster]) -> QueryBuilder<'_, Postgres> {
+    let mut qb = QueryBuilder::new(
+        "INSERT INTO symbol_master (
+            symbol, exchange, name, sector, industry, currency, country, ipo_date,
+            market_cap, is_active, data_source, last_updated
+        ) ",
+    );
+    qb.push_values(records, |mut b, record| {
+        b.push_bind(&record.symbol)
+            .push_bind(&record.exchange)
+            .push_bind(&record.name)
+            .push_bind(&record.sector)
+            .push_bind(&record.industry)
+            .push_bind(&record.currency)
+            .push_bind(&record.country)
+            .push_bind(record.ipo_date)
+            .push_bind(record.market_cap)
+            .push_bind(record.is_active)
+            .push_bind(&record.data_source)
+            .push_bind(record.last_updated);
+    });
+    qb.push(
+        " ON CONFLICT (symbol) DO UPDATE SET
+            exchange = EXCLUDED.exchange,
+            name = EXCLUDED.name,
+            sector = EXCLUDED.sector,
+            industry = EXCLUDED.industry,
+            currency = EXCLUDED.currency,
+            country = EXCLUDED.country,
+            ipo_date = EXCLUDED.ipo_date,
+            market_cap = EXCLUDED.market_cap,
+            is_active = EXCLUDED.is_active,
+            data_source = EXCLUDED.data_source,
+            last_updated = EXCLUDED.last_updated",
+    );
+    qb
+}
+
+/// Upserts all `records` atomically within `tx`, batching them into
+/// multi-row statements instead of one `execute` per record.
+pub async fn upsert_symbols(
+    tx: &mut Transaction<'_, Postgres>,
+    records: &[SymbolMaster],
+) -> Result<(), sqlx::Error> {
+    for chunk in records.chunks(MAX_ROWS_PER_STATEMENT) {
+        build_symbol_upsert_statement(chunk)
+            .build()
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+
+    fn sample_record(symbol: &str) -> SymbolMaster {
+        SymbolMaster {
+            symbol: symbol.to_string(),
+            exchange: Some("NASDAQ".to_string()),
+            name: Some("Acme Corp".to_string()),
+            sector: Some("Technology".to_string()),
+            industry: Some("Software".to_string()),
+            currency: Some("USD".to_string()),
+            country: Some("US".to_string()),
+            ipo_date: NaiveDate::from_ymd_opt(2020, 1, 1),
+            market_cap: Some(1_000_000),
+            is_active: true,
+            data_source: "Finnhub".to_string(),
+            last_updated: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn statement_lists_columns_and_excluded_assignments_in_bind_order() {
+        let records = vec![sample_record("AAA")];
+        let qb = build_symbol_upsert_statement(&records);
+        let sql = qb.sql();
+
+        assert!(sql.contains(
+            "symbol, exchange, name, sector, industry, currency, country, ipo_date,"
+        ));
+        assert!(sql.contains("market_cap, is_active, data_source, last_updated"));
+        assert!(sql.contains("($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"));
+        assert!(sql.contains("ON CONFLICT (symbol) DO UPDATE SET"));
+        assert!(sql.contains("exchange = EXCLUDED.exchange"));
+        assert!(sql.contains("last_updated = EXCLUDED.last_updated"));
+    }
+
+    #[test]
+    fn statement_binds_one_parameter_group_per_row() {
+        let records = vec![sample_record("AAA"), sample_record("BBB")];
+        let qb = build_symbol_upsert_statement(&records);
+        let sql = qb.sql();
+
+        assert!(sql.contains("($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"));
+        assert!(sql.contains("($13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24)"));
+    }
+
+    #[test]
+    fn chunks_at_max_rows_per_statement() {
+        const COLUMNS_PER_ROW: usize = 12;
+
+        let records: Vec<SymbolMaster> = (0..MAX_ROWS_PER_STATEMENT + 1)
+            .map(|i| sample_record(&format!("SYM{}", i)))
+            .collect();
+
+        let chunks: Vec<&[SymbolMaster]> = records.chunks(MAX_ROWS_PER_STATEMENT).collect();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), MAX_ROWS_PER_STATEMENT);
+        assert_eq!(chunks[1].len(), 1);
+
+        for chunk in &chunks {
+            let sql = build_symbol_upsert_statement(chunk).sql().to_string();
+            assert_eq!(sql.matches('$').count(), chunk.len() * COLUMNS_PER_ROW);
+        }
+    }
+}